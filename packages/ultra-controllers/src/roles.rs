@@ -1,15 +1,14 @@
 // based on https://github.com/CosmWasm/cw-plus/blob/main/packages/controllers/src/admin.rs
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fmt;
 use thiserror::Error;
 
-use cosmwasm_std::{
-    attr, Addr, CustomQuery, Deps, DepsMut, MessageInfo, Response, StdError, StdResult, Storage,
-};
-use cw_storage_plus::{index_list, IndexedMap, Item, MultiIndex};
+use cosmwasm_std::{attr, Addr, Attribute, Order, StdError, StdResult, Storage};
+use cw_storage_plus::{index_list, IndexedMap, Item, Map, MultiIndex};
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum Role {
     ActivePool,
@@ -18,6 +17,30 @@ pub enum Role {
     StabilityPool,
 }
 
+impl Role {
+    /// Every role the contract knows about, used to seed `resolve_roles`.
+    fn all() -> [Role; 4] {
+        [
+            Role::ActivePool,
+            Role::TroveManager,
+            Role::Owner,
+            Role::StabilityPool,
+        ]
+    }
+
+    /// The inverse of `to_string`, used to turn a stored role pk back into its
+    /// enum variant when walking the parent hierarchy or the `roles_by_addr` index.
+    fn from_pk(pk: &str) -> Option<Role> {
+        match pk {
+            "active_pool" => Some(Role::ActivePool),
+            "trove_manager" => Some(Role::TroveManager),
+            "owner" => Some(Role::Owner),
+            "stability_pool" => Some(Role::StabilityPool),
+            _ => None,
+        }
+    }
+}
+
 impl ToString for Role {
     fn to_string(&self) -> String {
         match &self {
@@ -38,6 +61,9 @@ pub enum RolesError {
 
     #[error("Caller is not {label}")]
     UnauthorizedForRole { label: String },
+
+    #[error("{label} cannot be its own parent")]
+    SelfLoop { label: String },
 }
 
 pub type RoleRecord = Addr;
@@ -45,52 +71,412 @@ pub type RoleRecord = Addr;
 /// stringified role
 pub type RolePK<'a> = &'a str;
 
+/// primary key of a single grant: a role paired with one of its (possibly many) members
+pub type MemberPK<'a> = (RolePK<'a>, &'a Addr);
+
 #[index_list(RoleRecord)]
 pub struct RolesIndexes<'a> {
     // find all roles for one address
     // allow for edge case where one address has multiple roles.
     // e.g. `owner` is also `generator`
-    roles_by_addr: MultiIndex<'a, Addr, RoleRecord, RolePK<'a>>,
+    roles_by_addr: MultiIndex<'a, Addr, RoleRecord, MemberPK<'a>>,
 }
 
 pub struct RoleConsumer<'a>(Item<'a, Addr>);
 
 // state/logic
-pub struct RoleProvider<'a>(IndexedMap<'a, RolePK<'a>, RoleRecord, RolesIndexes<'a>>);
+pub struct RoleProvider<'a> {
+    roles: IndexedMap<'a, MemberPK<'a>, RoleRecord, RolesIndexes<'a>>,
+    // candidate grantee awaiting `accept_role`, keyed by the same role pk as `roles`.
+    // kept separate so a pending proposal never counts toward `has_role`/`assert_role`.
+    pending: Map<'a, RolePK<'a>, Addr>,
+    // direct parents of a role, e.g. `trove_manager` -> `[owner]`. `resolve_roles`
+    // walks this to let a parent grant implicitly satisfy a child role check.
+    parents: Map<'a, RolePK<'a>, Vec<String>>,
+    // admin role required to `grant`/`revoke` a role. absent means Owner.
+    admins: Map<'a, RolePK<'a>, String>,
+    // dotted permission patterns directly granted to a role, e.g. `["active_pool.*"]`.
+    permissions: Map<'a, RolePK<'a>, Vec<String>>,
+}
 
 // this is the core business logic we expose
 impl<'a> RoleProvider<'a> {
-    pub fn new(namespace: &'a str, roles_by_addr_idx_namespace: &'a str) -> Self {
-        RoleProvider(IndexedMap::new(
-            namespace,
-            RolesIndexes::<'a> {
-                roles_by_addr: MultiIndex::new(
-                    |addr| addr.clone(),
-                    namespace,
-                    roles_by_addr_idx_namespace,
-                ),
-            },
-        ))
+    pub fn new(
+        namespace: &'a str,
+        roles_by_addr_idx_namespace: &'a str,
+        pending_namespace: &'a str,
+        parents_namespace: &'a str,
+        admins_namespace: &'a str,
+        permissions_namespace: &'a str,
+    ) -> Self {
+        RoleProvider {
+            roles: IndexedMap::new(
+                namespace,
+                RolesIndexes::<'a> {
+                    roles_by_addr: MultiIndex::new(
+                        |addr| addr.clone(),
+                        namespace,
+                        roles_by_addr_idx_namespace,
+                    ),
+                },
+            ),
+            pending: Map::new(pending_namespace),
+            parents: Map::new(parents_namespace),
+            admins: Map::new(admins_namespace),
+            permissions: Map::new(permissions_namespace),
+        }
+    }
+
+    /// The role required to `grant`/`revoke` `role`. Owner is its own admin and the
+    /// default admin for every role that hasn't configured one via `set_admin`.
+    pub fn admin_of(&self, store: &dyn Storage, role: &Role) -> StdResult<Role> {
+        match self.admins.may_load(store, &role.to_string())? {
+            Some(admin_pk) => Role::from_pk(&admin_pk).ok_or_else(|| {
+                StdError::generic_err(format!("unknown admin role pk: {admin_pk}"))
+            }),
+            None => Ok(Role::Owner),
+        }
+    }
+
+    /// Configures the admin role for `role`. Only the Owner may edit admin relations.
+    pub fn set_admin(
+        &self,
+        store: &mut dyn Storage,
+        role: &Role,
+        admin: &Role,
+        caller: &Addr,
+    ) -> Result<(), RolesError> {
+        self.assert_role(store, &Role::Owner, caller)?;
+        self.admins.save(store, &role.to_string(), &admin.to_string())?;
+        Ok(())
+    }
+
+    /// Adds `addr` to the set of addresses holding `role`. `caller` must hold
+    /// `role`'s admin role (see `admin_of`). Returns attributes suitable for a
+    /// `Response` so indexers can audit the privilege change.
+    pub fn grant(
+        &self,
+        store: &mut dyn Storage,
+        role: &Role,
+        addr: Addr,
+        caller: &Addr,
+    ) -> Result<Vec<Attribute>, RolesError> {
+        let admin = self.admin_of(store, role)?;
+        self.assert_role(store, &admin, caller)?;
+        self.roles.save(store, (&role.to_string(), &addr), &addr)?;
+        Ok(vec![
+            attr("action", "grant_role"),
+            attr("role", role.to_string()),
+            attr("grantee", addr),
+        ])
+    }
+
+    /// Removes `addr` from the set of addresses holding `role`. `caller` must hold
+    /// `role`'s admin role (see `admin_of`). Returns attributes suitable for a
+    /// `Response` so indexers can audit the privilege change.
+    pub fn revoke(
+        &self,
+        store: &mut dyn Storage,
+        role: &Role,
+        addr: Addr,
+        caller: &Addr,
+    ) -> Result<Vec<Attribute>, RolesError> {
+        let admin = self.admin_of(store, role)?;
+        self.assert_role(store, &admin, caller)?;
+        self.roles.remove(store, (&role.to_string(), &addr))?;
+        Ok(vec![
+            attr("action", "revoke_role"),
+            attr("role", role.to_string()),
+            attr("grantee", addr),
+        ])
+    }
+
+    /// Every address currently holding `role`.
+    pub fn members(&self, store: &dyn Storage, role: &Role) -> StdResult<Vec<Addr>> {
+        self.roles
+            .prefix(&role.to_string())
+            .range(store, None, None, Order::Ascending)
+            .map(|item| item.map(|(addr, _record)| addr))
+            .collect()
+    }
+
+    /// Every role directly assigned to `addr`, the inverse of `has_role`.
+    /// Walks the `roles_by_addr` index rather than scanning every role's
+    /// member set, analogous to `usersInfo`'s per-user role enumeration.
+    pub fn roles_of(&self, store: &dyn Storage, addr: &Addr) -> StdResult<Vec<Role>> {
+        self.roles
+            .idx
+            .roles_by_addr
+            .prefix(addr.clone())
+            .keys(store, None, None, Order::Ascending)
+            .map(|key| {
+                let (role_pk, _addr) = key?;
+                Role::from_pk(&role_pk).ok_or_else(|| {
+                    StdError::generic_err(format!("unknown role pk: {role_pk}"))
+                })
+            })
+            .collect()
+    }
+
+    /// Direct parents of `role`, as configured by `set_parents`.
+    pub fn parents(&self, store: &dyn Storage, role: &Role) -> StdResult<Vec<String>> {
+        Ok(self
+            .parents
+            .may_load(store, &role.to_string())?
+            .unwrap_or_default())
+    }
+
+    /// Overwrites the direct parent set of `role`. Only the Owner may edit the
+    /// hierarchy, and a role may never name itself as its own parent.
+    pub fn set_parents(
+        &self,
+        store: &mut dyn Storage,
+        role: &Role,
+        caller: &Addr,
+        parents: Vec<Role>,
+    ) -> Result<(), RolesError> {
+        self.assert_role(store, &Role::Owner, caller)?;
+        let role_pk = role.to_string();
+        if parents.iter().any(|parent| parent == role) {
+            return Err(RolesError::SelfLoop { label: role_pk });
+        }
+        let parent_pks = parents.iter().map(Role::to_string).collect();
+        self.parents.save(store, &role_pk, &parent_pks)?;
+        Ok(())
+    }
+
+    /// Computes the effective role set for `caller`: every role they directly
+    /// hold, plus every role for which some directly-held role is an ancestor
+    /// (a grant on a parent implicitly satisfies its children). For each
+    /// candidate role this walks up its own `parents` chain looking for a
+    /// directly-held role; a visited set makes cycles a no-op instead of an
+    /// infinite loop.
+    ///
+    /// Note this is the reverse of "seed a worklist with caller's directly-held
+    /// roles and walk each one's own parents into the result set" — that
+    /// reading grants a child-role holder its parent's (more privileged) role,
+    /// which is a privilege escalation. The direction here is intentional and
+    /// security-load-bearing: do not "simplify" it back.
+    pub fn resolve_roles(&self, store: &dyn Storage, caller: &Addr) -> StdResult<HashSet<Role>> {
+        let mut directly_held = HashSet::new();
+        for role in Role::all() {
+            if self.is_directly_granted(store, &role, caller)? {
+                directly_held.insert(role);
+            }
+        }
+
+        let mut resolved = HashSet::new();
+        for role in Role::all() {
+            if self.is_satisfied_by_ancestor(store, &role, &directly_held)? {
+                resolved.insert(role);
+            }
+        }
+
+        Ok(resolved)
+    }
+
+    /// Returns Ok(true) if `role` itself, or any ancestor reachable by walking
+    /// `parents` upward from `role`, is in `directly_held`.
+    fn is_satisfied_by_ancestor(
+        &self,
+        store: &dyn Storage,
+        role: &Role,
+        directly_held: &HashSet<Role>,
+    ) -> StdResult<bool> {
+        if directly_held.contains(role) {
+            return Ok(true);
+        }
+
+        let mut visited = HashSet::new();
+        let mut worklist = vec![role.to_string()];
+        while let Some(role_pk) = worklist.pop() {
+            if !visited.insert(role_pk.clone()) {
+                continue;
+            }
+            for parent_pk in self.parents.may_load(store, &role_pk)?.unwrap_or_default() {
+                if let Some(parent) = Role::from_pk(&parent_pk) {
+                    if directly_held.contains(&parent) {
+                        return Ok(true);
+                    }
+                }
+                worklist.push(parent_pk);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Permission patterns directly granted to `role`, as configured by
+    /// `set_permissions`.
+    pub fn permissions(&self, store: &dyn Storage, role: &Role) -> StdResult<Vec<String>> {
+        Ok(self
+            .permissions
+            .may_load(store, &role.to_string())?
+            .unwrap_or_default())
+    }
+
+    /// Overwrites the permission patterns granted to `role`, e.g.
+    /// `["active_pool.*"]`. Only the Owner may edit permission grants.
+    pub fn set_permissions(
+        &self,
+        store: &mut dyn Storage,
+        role: &Role,
+        caller: &Addr,
+        patterns: Vec<String>,
+    ) -> Result<(), RolesError> {
+        self.assert_role(store, &Role::Owner, caller)?;
+        self.permissions.save(store, &role.to_string(), &patterns)?;
+        Ok(())
     }
 
-    pub fn delete(&self, store: &mut dyn Storage, role: &Role) -> StdResult<()> {
-        self.0.remove(store, &role.to_string())
+    /// Returns Ok(true) if `caller` -- through any of their resolved roles --
+    /// holds a permission pattern matching `needed`, e.g. a role granted
+    /// `active_pool.*` satisfies a `needed` of `active_pool.mint`.
+    pub fn has_permission(
+        &self,
+        store: &dyn Storage,
+        caller: &Addr,
+        needed: &str,
+    ) -> StdResult<bool> {
+        for role in self.resolve_roles(store, caller)? {
+            for pattern in self.permissions(store, &role)? {
+                if permission_pattern_matches(&pattern, needed) {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
     }
 
-    pub fn set(&self, store: &mut dyn Storage, role: &Role, grantee: Addr) -> StdResult<()> {
-        self.0.save(store, &role.to_string(), &grantee)
+    /// Like has_permission but returns RolesError::UnauthorizedForRole (labeled
+    /// with the missing permission) if not authorized.
+    pub fn assert_permission(
+        &self,
+        store: &dyn Storage,
+        caller: &Addr,
+        needed: &str,
+    ) -> Result<(), RolesError> {
+        if self.has_permission(store, caller, needed)? {
+            Ok(())
+        } else {
+            Err(RolesError::UnauthorizedForRole {
+                label: needed.to_string(),
+            })
+        }
+    }
+
+    /// Clears every member of `role`. This is the original single-grantee API;
+    /// prefer `grant`/`revoke` for roles that may have several members. Returns
+    /// attributes suitable for a `Response` so indexers can audit the change.
+    pub fn delete(&self, store: &mut dyn Storage, role: &Role) -> StdResult<Vec<Attribute>> {
+        for addr in self.members(store, role)? {
+            self.roles.remove(store, (&role.to_string(), &addr))?;
+        }
+        Ok(vec![attr("action", "delete_role"), attr("role", role.to_string())])
+    }
+
+    /// Overwrites the full member set of `role` with exactly `grantee`. This is
+    /// the original single-grantee API, still the natural fit for exclusive
+    /// roles like Owner; prefer `grant`/`revoke` for roles with several members.
+    /// Returns attributes suitable for a `Response` so indexers can audit the change.
+    pub fn set(&self, store: &mut dyn Storage, role: &Role, grantee: Addr) -> StdResult<Vec<Attribute>> {
+        self.delete(store, role)?;
+        self.roles.save(store, (&role.to_string(), &grantee), &grantee)?;
+        Ok(vec![
+            attr("action", "set_role"),
+            attr("role", role.to_string()),
+            attr("grantee", grantee),
+        ])
     }
 
+    /// Returns an arbitrary member of `role`, or `None` if it has no members.
+    /// Meaningful mainly for roles expected to have exactly one grantee.
     pub fn get(&self, store: &dyn Storage, role: &Role) -> StdResult<Option<RoleRecord>> {
-        self.0.may_load(store, &role.to_string())
+        Ok(self.members(store, role)?.into_iter().next())
     }
 
-    /// Returns Ok(true) if this user has the role, Ok(false) if not and an Error if
-    /// we hit an error with Api or Storage usage
+    /// Returns the address proposed for `role` via `propose_role`, if any proposal
+    /// is pending acceptance.
+    pub fn pending(&self, store: &dyn Storage, role: &Role) -> StdResult<Option<Addr>> {
+        self.pending.may_load(store, &role.to_string())
+    }
+
+    /// Proposes `candidate` as the next grantee of `role`. This does not grant
+    /// `role` by itself: `candidate` must call `accept_role` to take effect, so a
+    /// mistyped address can never permanently lock out a privileged role. Modeled
+    /// on OpenZeppelin's Ownable2Step, `caller` must hold `role`'s admin role
+    /// (see `admin_of`), mirroring OZ's `onlyOwner`-gated `transferOwnership`.
+    pub fn propose_role(
+        &self,
+        store: &mut dyn Storage,
+        role: &Role,
+        candidate: Addr,
+        caller: &Addr,
+    ) -> Result<(), RolesError> {
+        let admin = self.admin_of(store, role)?;
+        self.assert_role(store, &admin, caller)?;
+        self.pending.save(store, &role.to_string(), &candidate)?;
+        Ok(())
+    }
+
+    /// Clears a pending proposal for `role` without granting it. `caller` must
+    /// hold `role`'s admin role (see `admin_of`).
+    pub fn cancel_proposal(
+        &self,
+        store: &mut dyn Storage,
+        role: &Role,
+        caller: &Addr,
+    ) -> Result<(), RolesError> {
+        let admin = self.admin_of(store, role)?;
+        self.assert_role(store, &admin, caller)?;
+        self.pending.remove(store, &role.to_string());
+        Ok(())
+    }
+
+    /// Adds the pending candidate for `role` to its member set. Only the
+    /// proposed candidate may accept; anyone else is rejected as unauthorized.
+    /// This grants `candidate` alongside any existing members rather than
+    /// replacing them, so accepting a proposal on a multi-grantee role never
+    /// revokes its other members.
+    pub fn accept_role(
+        &self,
+        store: &mut dyn Storage,
+        role: &Role,
+        caller: &Addr,
+    ) -> Result<(), RolesError> {
+        let candidate = self
+            .pending(store, role)?
+            .ok_or_else(|| RolesError::UnauthorizedForRole {
+                label: role.to_string(),
+            })?;
+        if &candidate != caller {
+            return Err(RolesError::UnauthorizedForRole {
+                label: role.to_string(),
+            });
+        }
+        self.roles
+            .save(store, (&role.to_string(), &candidate), &candidate)?;
+        self.pending.remove(store, &role.to_string());
+        Ok(())
+    }
+
+    /// Checks only whether `caller` is a direct member of `role`, ignoring
+    /// inherited parent roles. Used by `resolve_roles` to collect the caller's
+    /// directly-held roles.
+    fn is_directly_granted(
+        &self,
+        store: &dyn Storage,
+        role: &Role,
+        caller: &Addr,
+    ) -> StdResult<bool> {
+        Ok(self.roles.may_load(store, (&role.to_string(), caller))?.is_some())
+    }
+
+    /// Returns Ok(true) if this user has the role -- directly or via an inherited
+    /// parent role -- Ok(false) if not, and an Error if we hit an error with Api
+    /// or Storage usage.
     pub fn has_role(&self, store: &dyn Storage, role: &Role, caller: &Addr) -> StdResult<bool> {
-        self.0
-            .may_load(store, &role.to_string())?
-            .map_or_else(|| Ok(false), |addr| Ok(&addr == caller))
+        Ok(self.resolve_roles(store, caller)?.contains(role))
     }
 
     /// Returns Ok(true) if this user has any of the roles, Ok(false) if not and an Error if
@@ -154,6 +540,27 @@ impl<'a> RoleProvider<'a> {
     }
 }
 
+/// Matches a dotted permission `pattern` (e.g. `active_pool.*`, `lab.*.admin`)
+/// against a `needed` permission string, segment-by-segment on `.`. A `*`
+/// segment matches any single segment; a trailing `*` matches one or more
+/// remaining segments.
+fn permission_pattern_matches(pattern: &str, needed: &str) -> bool {
+    let pattern_segs: Vec<&str> = pattern.split('.').collect();
+    let needed_segs: Vec<&str> = needed.split('.').collect();
+
+    for (i, pattern_seg) in pattern_segs.iter().enumerate() {
+        if *pattern_seg == "*" && i == pattern_segs.len() - 1 {
+            return needed_segs.len() > i;
+        }
+        match needed_segs.get(i) {
+            Some(needed_seg) if pattern_seg == needed_seg || *pattern_seg == "*" => continue,
+            _ => return false,
+        }
+    }
+
+    pattern_segs.len() == needed_segs.len()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -164,7 +571,7 @@ mod tests {
     #[test]
     fn set_and_get_owner() {
         let mut deps = mock_dependencies();
-        let control = RoleProvider::new("foo", "foo__roles_by_addr");
+        let control = RoleProvider::new("foo", "foo__roles_by_addr", "foo__pending", "foo__parents", "foo__admins", "foo__permissions");
 
         // initialize and check
         let owner = Addr::unchecked("owner");
@@ -184,7 +591,7 @@ mod tests {
     fn role_checks() {
         let mut deps = mock_dependencies();
 
-        let control = RoleProvider::new("foo", "foo__idx");
+        let control = RoleProvider::new("foo", "foo__idx", "foo__pending", "foo__parents", "foo__admins", "foo__permissions");
         let owner = Addr::unchecked("big boss");
         let imposter = Addr::unchecked("imposter");
 
@@ -287,4 +694,492 @@ mod tests {
             err
         );
     }
+
+    #[test]
+    fn two_step_ownership_transfer() {
+        let mut deps = mock_dependencies();
+        let control = RoleProvider::new("foo", "foo__idx", "foo__pending", "foo__parents", "foo__admins", "foo__permissions");
+
+        let owner = Addr::unchecked("big boss");
+        let successor = Addr::unchecked("successor");
+        let imposter = Addr::unchecked("imposter");
+
+        control
+            .set(deps.as_mut().storage, &Role::Owner, owner.clone())
+            .unwrap();
+
+        // only the role's admin (Owner is its own admin by default) may propose
+        let err = control
+            .propose_role(
+                deps.as_mut().storage,
+                &Role::Owner,
+                successor.clone(),
+                &imposter,
+            )
+            .unwrap_err();
+        assert_eq!(
+            RolesError::UnauthorizedForRole {
+                label: Role::Owner.to_string()
+            },
+            err
+        );
+
+        // proposing a candidate must not grant the role yet
+        control
+            .propose_role(
+                deps.as_mut().storage,
+                &Role::Owner,
+                successor.clone(),
+                &owner,
+            )
+            .unwrap();
+        assert_eq!(
+            Some(successor.clone()),
+            control.pending(deps.as_ref().storage, &Role::Owner).unwrap()
+        );
+        assert!(control
+            .has_role(deps.as_ref().storage, &Role::Owner, &successor)
+            .unwrap()
+            == false);
+        control
+            .assert_role(deps.as_ref().storage, &Role::Owner, &owner)
+            .unwrap();
+
+        // only the proposed candidate may accept
+        let err = control
+            .accept_role(deps.as_mut().storage, &Role::Owner, &imposter)
+            .unwrap_err();
+        assert_eq!(
+            RolesError::UnauthorizedForRole {
+                label: Role::Owner.to_string()
+            },
+            err
+        );
+
+        control
+            .accept_role(deps.as_mut().storage, &Role::Owner, &successor)
+            .unwrap();
+        assert!(control
+            .has_role(deps.as_ref().storage, &Role::Owner, &successor)
+            .unwrap());
+        // accepting adds the candidate alongside existing members rather than
+        // replacing them, since roles are multi-grantee; a full handover that
+        // drops the old owner calls `revoke` separately.
+        assert!(control
+            .has_role(deps.as_ref().storage, &Role::Owner, &owner)
+            .unwrap());
+        assert_eq!(
+            None,
+            control.pending(deps.as_ref().storage, &Role::Owner).unwrap()
+        );
+
+        // only the role's admin may cancel a proposal
+        control
+            .propose_role(
+                deps.as_mut().storage,
+                &Role::Owner,
+                imposter.clone(),
+                &owner,
+            )
+            .unwrap();
+        let err = control
+            .cancel_proposal(deps.as_mut().storage, &Role::Owner, &imposter)
+            .unwrap_err();
+        assert_eq!(
+            RolesError::UnauthorizedForRole {
+                label: Role::Owner.to_string()
+            },
+            err
+        );
+
+        // a cancelled proposal never becomes acceptable
+        control
+            .cancel_proposal(deps.as_mut().storage, &Role::Owner, &owner)
+            .unwrap();
+        let err = control
+            .accept_role(deps.as_mut().storage, &Role::Owner, &imposter)
+            .unwrap_err();
+        assert_eq!(
+            RolesError::UnauthorizedForRole {
+                label: Role::Owner.to_string()
+            },
+            err
+        );
+    }
+
+    #[test]
+    fn role_inheritance_resolves_through_parents() {
+        let mut deps = mock_dependencies();
+        let control = RoleProvider::new("foo", "foo__idx", "foo__pending", "foo__parents", "foo__admins", "foo__permissions");
+
+        let owner = Addr::unchecked("big boss");
+        let keeper = Addr::unchecked("keeper");
+        control
+            .set(deps.as_mut().storage, &Role::Owner, owner.clone())
+            .unwrap();
+        control
+            .set(deps.as_mut().storage, &Role::TroveManager, keeper.clone())
+            .unwrap();
+
+        // trove_manager inherits from owner, so owner implicitly satisfies it too
+        control
+            .set_parents(
+                deps.as_mut().storage,
+                &Role::TroveManager,
+                &owner,
+                vec![Role::Owner],
+            )
+            .unwrap();
+        assert!(control
+            .has_role(deps.as_ref().storage, &Role::TroveManager, &owner)
+            .unwrap());
+        assert!(control
+            .has_role(deps.as_ref().storage, &Role::TroveManager, &keeper)
+            .unwrap());
+        // inheritance is one-directional: trove_manager's holder doesn't become owner
+        assert!(!control
+            .has_role(deps.as_ref().storage, &Role::Owner, &keeper)
+            .unwrap());
+
+        // only the owner may edit the hierarchy
+        let err = control
+            .set_parents(
+                deps.as_mut().storage,
+                &Role::StabilityPool,
+                &keeper,
+                vec![Role::Owner],
+            )
+            .unwrap_err();
+        assert_eq!(
+            RolesError::UnauthorizedForRole {
+                label: Role::Owner.to_string()
+            },
+            err
+        );
+
+        // a role can't be its own parent
+        let err = control
+            .set_parents(
+                deps.as_mut().storage,
+                &Role::ActivePool,
+                &owner,
+                vec![Role::ActivePool],
+            )
+            .unwrap_err();
+        assert_eq!(
+            RolesError::SelfLoop {
+                label: Role::ActivePool.to_string()
+            },
+            err
+        );
+
+        // a cycle is tolerated: it just stops expanding rather than looping forever
+        control
+            .set_parents(
+                deps.as_mut().storage,
+                &Role::ActivePool,
+                &owner,
+                vec![Role::StabilityPool],
+            )
+            .unwrap();
+        control
+            .set_parents(
+                deps.as_mut().storage,
+                &Role::StabilityPool,
+                &owner,
+                vec![Role::ActivePool],
+            )
+            .unwrap();
+        control
+            .set(deps.as_mut().storage, &Role::ActivePool, keeper.clone())
+            .unwrap();
+        assert!(control
+            .has_role(deps.as_ref().storage, &Role::StabilityPool, &keeper)
+            .unwrap());
+    }
+
+    #[test]
+    fn multiple_grantees_with_per_role_admin() {
+        let mut deps = mock_dependencies();
+        let control = RoleProvider::new(
+            "foo",
+            "foo__idx",
+            "foo__pending",
+            "foo__parents",
+            "foo__admins",
+            "foo__permissions",
+        );
+
+        let owner = Addr::unchecked("big boss");
+        let keeper_a = Addr::unchecked("keeper a");
+        let keeper_b = Addr::unchecked("keeper b");
+        control
+            .set(deps.as_mut().storage, &Role::Owner, owner.clone())
+            .unwrap();
+
+        // owner is the default admin for every role
+        control
+            .grant(
+                deps.as_mut().storage,
+                &Role::TroveManager,
+                keeper_a.clone(),
+                &owner,
+            )
+            .unwrap();
+        control
+            .grant(
+                deps.as_mut().storage,
+                &Role::TroveManager,
+                keeper_b.clone(),
+                &owner,
+            )
+            .unwrap();
+        assert_eq!(
+            vec![keeper_a.clone(), keeper_b.clone()],
+            control
+                .members(deps.as_ref().storage, &Role::TroveManager)
+                .unwrap()
+        );
+        assert!(control
+            .has_role(deps.as_ref().storage, &Role::TroveManager, &keeper_a)
+            .unwrap());
+        assert!(control
+            .has_role(deps.as_ref().storage, &Role::TroveManager, &keeper_b)
+            .unwrap());
+
+        // keeper_a can't revoke keeper_b: they aren't trove_manager's admin
+        let err = control
+            .revoke(
+                deps.as_mut().storage,
+                &Role::TroveManager,
+                keeper_b.clone(),
+                &keeper_a,
+            )
+            .unwrap_err();
+        assert_eq!(
+            RolesError::UnauthorizedForRole {
+                label: Role::Owner.to_string()
+            },
+            err
+        );
+
+        // delegate trove_manager administration to keeper_a
+        control
+            .set_admin(
+                deps.as_mut().storage,
+                &Role::TroveManager,
+                &Role::TroveManager,
+                &owner,
+            )
+            .unwrap();
+        assert_eq!(
+            Role::TroveManager,
+            control
+                .admin_of(deps.as_ref().storage, &Role::TroveManager)
+                .unwrap()
+        );
+        control
+            .revoke(
+                deps.as_mut().storage,
+                &Role::TroveManager,
+                keeper_b.clone(),
+                &keeper_a,
+            )
+            .unwrap();
+        assert_eq!(
+            vec![keeper_a.clone()],
+            control
+                .members(deps.as_ref().storage, &Role::TroveManager)
+                .unwrap()
+        );
+        assert!(!control
+            .has_role(deps.as_ref().storage, &Role::TroveManager, &keeper_b)
+            .unwrap());
+    }
+
+    #[test]
+    fn permission_pattern_matching() {
+        assert!(permission_pattern_matches("active_pool.*", "active_pool.mint"));
+        assert!(permission_pattern_matches("active_pool.*", "active_pool.burn"));
+        assert!(!permission_pattern_matches("active_pool.*", "trove_manager.mint"));
+        assert!(permission_pattern_matches("lab.test.admin", "lab.test.admin"));
+        assert!(!permission_pattern_matches("lab.test.admin", "lab.test"));
+        assert!(permission_pattern_matches("lab.*.admin", "lab.test.admin"));
+        assert!(!permission_pattern_matches("lab.*.admin", "lab.test.user"));
+    }
+
+    #[test]
+    fn has_permission_resolves_through_roles() {
+        let mut deps = mock_dependencies();
+        let control = RoleProvider::new(
+            "foo",
+            "foo__idx",
+            "foo__pending",
+            "foo__parents",
+            "foo__admins",
+            "foo__permissions",
+        );
+
+        let owner = Addr::unchecked("big boss");
+        let keeper = Addr::unchecked("keeper");
+        control
+            .set(deps.as_mut().storage, &Role::Owner, owner.clone())
+            .unwrap();
+        control
+            .set(deps.as_mut().storage, &Role::TroveManager, keeper.clone())
+            .unwrap();
+
+        control
+            .set_permissions(
+                deps.as_mut().storage,
+                &Role::TroveManager,
+                &owner,
+                vec!["trove.liquidate".to_string(), "trove.redeem".to_string()],
+            )
+            .unwrap();
+
+        assert!(control
+            .has_permission(deps.as_ref().storage, &keeper, "trove.liquidate")
+            .unwrap());
+        assert!(!control
+            .has_permission(deps.as_ref().storage, &keeper, "trove.open")
+            .unwrap());
+        // owner does not yet hold trove_manager's permissions: nothing has
+        // made owner an ancestor of trove_manager in the hierarchy yet
+        assert!(!control
+            .has_permission(deps.as_ref().storage, &owner, "trove.liquidate")
+            .unwrap());
+
+        let err = control
+            .assert_permission(deps.as_ref().storage, &owner, "trove.liquidate")
+            .unwrap_err();
+        assert_eq!(
+            RolesError::UnauthorizedForRole {
+                label: "trove.liquidate".to_string()
+            },
+            err
+        );
+
+        // owner inherits trove_manager's grants once it's a parent
+        control
+            .set_parents(
+                deps.as_mut().storage,
+                &Role::TroveManager,
+                &owner,
+                vec![Role::Owner],
+            )
+            .unwrap();
+        assert!(control
+            .has_permission(deps.as_ref().storage, &owner, "trove.liquidate")
+            .unwrap());
+    }
+
+    #[test]
+    fn roles_of_reverse_lookup() {
+        let mut deps = mock_dependencies();
+        let control = RoleProvider::new(
+            "foo",
+            "foo__idx",
+            "foo__pending",
+            "foo__parents",
+            "foo__admins",
+            "foo__permissions",
+        );
+
+        let owner = Addr::unchecked("big boss");
+        control
+            .set(deps.as_mut().storage, &Role::Owner, owner.clone())
+            .unwrap();
+        control
+            .grant(
+                deps.as_mut().storage,
+                &Role::TroveManager,
+                owner.clone(),
+                &owner,
+            )
+            .unwrap();
+
+        let mut roles = control.roles_of(deps.as_ref().storage, &owner).unwrap();
+        roles.sort_by_key(|r| r.to_string());
+        let mut expected = vec![Role::Owner, Role::TroveManager];
+        expected.sort_by_key(|r| r.to_string());
+        assert_eq!(expected, roles);
+
+        assert_eq!(
+            Vec::<Role>::new(),
+            control
+                .roles_of(deps.as_ref().storage, &Addr::unchecked("nobody"))
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn mutations_emit_attributes() {
+        let mut deps = mock_dependencies();
+        let control = RoleProvider::new(
+            "foo",
+            "foo__idx",
+            "foo__pending",
+            "foo__parents",
+            "foo__admins",
+            "foo__permissions",
+        );
+
+        let owner = Addr::unchecked("big boss");
+        let keeper = Addr::unchecked("keeper");
+
+        let attrs = control
+            .set(deps.as_mut().storage, &Role::Owner, owner.clone())
+            .unwrap();
+        assert_eq!(
+            vec![
+                attr("action", "set_role"),
+                attr("role", Role::Owner.to_string()),
+                attr("grantee", owner.clone()),
+            ],
+            attrs
+        );
+
+        let attrs = control
+            .grant(
+                deps.as_mut().storage,
+                &Role::TroveManager,
+                keeper.clone(),
+                &owner,
+            )
+            .unwrap();
+        assert_eq!(
+            vec![
+                attr("action", "grant_role"),
+                attr("role", Role::TroveManager.to_string()),
+                attr("grantee", keeper.clone()),
+            ],
+            attrs
+        );
+
+        let attrs = control
+            .revoke(
+                deps.as_mut().storage,
+                &Role::TroveManager,
+                keeper.clone(),
+                &owner,
+            )
+            .unwrap();
+        assert_eq!(
+            vec![
+                attr("action", "revoke_role"),
+                attr("role", Role::TroveManager.to_string()),
+                attr("grantee", keeper),
+            ],
+            attrs
+        );
+
+        let attrs = control
+            .delete(deps.as_mut().storage, &Role::Owner)
+            .unwrap();
+        assert_eq!(
+            vec![attr("action", "delete_role"), attr("role", Role::Owner.to_string())],
+            attrs
+        );
+    }
 }
\ No newline at end of file